@@ -0,0 +1,531 @@
+use crate::amount::{raw_to_decimal, Amount, AmountError, NonNegative, OverdraftAllowed};
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use std::cmp::min;
+use std::convert::TryFrom;
+use std::fmt;
+
+/// A single row of a printed/exported statement: either a transaction that
+/// moved money, or a `BalanceAssertion` checkpoint recorded against the
+/// account's history.
+#[derive(PartialEq, Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum StatementLine {
+    Transaction { date: DateTime<Utc>, amount: Decimal, balance: Decimal },
+    BalanceAssertion { date: DateTime<Utc>, expected: Decimal, actual: Decimal },
+}
+
+impl StatementLine {
+    #[allow(dead_code)]
+    fn header() -> String {
+        format!("{:^30} | {:>10} | {:>10}", "Date", "Amount", "Balance")
+    }
+
+    fn date(&self) -> DateTime<Utc> {
+        match self {
+            StatementLine::Transaction { date, .. } => *date,
+            StatementLine::BalanceAssertion { date, .. } => *date,
+        }
+    }
+}
+
+impl fmt::Display for StatementLine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StatementLine::Transaction { date, amount, balance } => write!(
+                f,
+                "{:^30} | {:>10.4} | {:>10.4}",
+                date.format("%Y-%m-%d %H:%M:%S%.f").to_string(), amount, balance
+            ),
+            StatementLine::BalanceAssertion { date, expected, actual } => write!(
+                f,
+                "{:^30} | assert balance = {:.4} (was {:.4})",
+                date.format("%Y-%m-%d %H:%M:%S%.f").to_string(), expected, actual
+            ),
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Serialize)]
+pub struct AccountStatement {
+    lines: Vec<StatementLine>
+}
+// would love to iterate on this one directly
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+enum OperationType {
+    Withdraw,
+    Deposit,
+}
+
+pub type TransactionId = u32;
+
+#[derive(PartialEq, Debug)]
+struct Operation {
+    operation_type: OperationType,
+    tx_id: TransactionId,
+    amount: Amount<NonNegative>,
+    date: DateTime<Utc>,
+    // a disputed deposit has its amount moved from available to held
+    disputed: bool,
+    // a charged back deposit is removed from the total altogether
+    charged_back: bool,
+}
+
+impl Operation {
+    // signed, ten-thousandths denominated contribution of this operation to the balance
+    fn value(&self) -> i64 {
+        match self.operation_type {
+            OperationType::Deposit => self.amount.value(),
+            OperationType::Withdraw => -self.amount.value(),
+        }
+    }
+
+}
+
+// a point-in-time balance checkpoint recorded via `assert_balance`
+#[derive(PartialEq, Debug)]
+struct Checkpoint {
+    at: DateTime<Utc>,
+    expected: Decimal,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct BankAccount {
+    operations: Vec<Operation>,
+    checkpoints: Vec<Checkpoint>,
+    pub locked: bool,
+}
+
+impl BankAccount {
+    pub fn new() -> BankAccount {
+        BankAccount {
+            operations: vec![],
+            checkpoints: vec![],
+            locked: false,
+        }
+    }
+
+    // held funds: deposits currently under dispute, in ten-thousandths
+    pub fn held(&self) -> i64 {
+        self.operations.iter()
+            .filter(|op| op.disputed)
+            .map(|op| op.value())
+            .sum()
+    }
+
+    // total funds, excluding deposits that have been charged back, in ten-thousandths
+    pub fn total(&self) -> i64 {
+        self.operations.iter()
+            .filter(|op| !op.charged_back)
+            .map(|op| op.value())
+            .sum()
+    }
+
+    pub fn balance(&self) -> Result<Amount<OverdraftAllowed>, AmountError> {
+        Amount::try_from(self.total() - self.held())
+    }
+
+    // running balance considering only operations dated at or before `at`
+    fn balance_at(&self, at: DateTime<Utc>) -> Decimal {
+        let raw: i64 = self.operations.iter()
+            .filter(|op| !op.charged_back && op.date <= at)
+            .map(|op| op.value())
+            .sum();
+        raw_to_decimal(raw)
+    }
+
+    /// Asserts that the balance at `at` was `expected`, also recording the
+    /// checkpoint so it shows up interleaved with transactions in `to_statement`.
+    #[allow(dead_code)]
+    pub fn assert_balance(&mut self, expected: Decimal, at: DateTime<Utc>) -> Result<(), String> {
+        let actual = self.balance_at(at);
+        self.checkpoints.push(Checkpoint { at, expected });
+        if actual == expected {
+            Ok(())
+        } else {
+            Err(format!("expected balance {} at {} but found {}", expected, at, actual))
+        }
+    }
+
+    /// Projects the balance forward from the earliest operation, capitalizing
+    /// `rate` interest at every `period`-sized step up to `until` and folding
+    /// in the deposits/withdrawals that fall within each step.
+    #[allow(dead_code)]
+    pub fn project_balance(&self, rate: Decimal, period: Duration, until: DateTime<Utc>) -> Decimal {
+        let start = match self.operations.iter().map(|op| op.date).min() {
+            Some(date) => date,
+            None => return Decimal::ZERO,
+        };
+
+        // no full period has elapsed yet; just reflect operations at or before `until`
+        if until <= start {
+            return self.balance_at(until);
+        }
+
+        let mut balance = Decimal::ZERO;
+        let mut step_start = start;
+        while step_start < until {
+            let step_end = min(step_start + period, until);
+
+            // interest capitalizes on the balance carried into the step; a
+            // negative balance accrues no interest
+            if balance > Decimal::ZERO {
+                balance += balance * rate;
+            }
+
+            // steps are otherwise half-open [step_start, step_end), but the
+            // final step must include `until` itself to match balance_at
+            let step_total: i64 = self.operations.iter()
+                .filter(|op| !op.charged_back && op.date >= step_start && (op.date < step_end || (step_end == until && op.date == until)))
+                .map(|op| op.value())
+                .sum();
+            balance += Decimal::new(step_total, 4);
+
+            step_start = step_end;
+        }
+        balance
+    }
+
+    pub fn make_deposit(&mut self, tx_id: TransactionId, money: Amount<NonNegative>, date: DateTime<Utc>) -> Result<(), &str> {
+        if self.locked {
+            return Err("Account is locked");
+        }
+        self.operations.push(Operation {
+            operation_type: OperationType::Deposit,
+            tx_id,
+            amount: money,
+            date,
+            disputed: false,
+            charged_back: false,
+        });
+        Ok(())
+    }
+
+    pub fn make_withdrawal(&mut self, tx_id: TransactionId, money: Amount<NonNegative>, date: DateTime<Utc>) -> Result<(), &str> {
+        if self.locked {
+            return Err("Account is locked");
+        }
+        let current_balance = self.balance().map_err(|_| "Not enough money")?;
+        let typed_amount = money.constrain::<OverdraftAllowed>().map_err(|_| "Not enough money")?;
+        match current_balance - typed_amount {
+            Ok(_) => {
+                self.operations.push(Operation {
+                    operation_type: OperationType::Withdraw,
+                    tx_id,
+                    amount: money,
+                    date,
+                    disputed: false,
+                    charged_back: false,
+                });
+                Ok(())
+            }
+            Err(_) => Err("Not enough money"),
+        }
+    }
+
+    fn find_disputable(&mut self, tx_id: TransactionId) -> Option<&mut Operation> {
+        self.operations.iter_mut()
+            .find(|op| op.tx_id == tx_id && op.operation_type == OperationType::Deposit && !op.charged_back)
+    }
+
+    // disputing an unknown or already disputed transaction is a no-op
+    pub fn dispute(&mut self, tx_id: TransactionId) {
+        if let Some(op) = self.find_disputable(tx_id) {
+            if !op.disputed {
+                op.disputed = true;
+            }
+        }
+    }
+
+    // resolving a transaction that is not under dispute is a no-op
+    pub fn resolve(&mut self, tx_id: TransactionId) {
+        if let Some(op) = self.find_disputable(tx_id) {
+            if op.disputed {
+                op.disputed = false;
+            }
+        }
+    }
+
+    // charging back a transaction that is not under dispute is a no-op
+    pub fn chargeback(&mut self, tx_id: TransactionId) {
+        if let Some(op) = self.find_disputable(tx_id) {
+            if op.disputed {
+                op.disputed = false;
+                op.charged_back = true;
+                self.locked = true;
+            }
+        }
+    }
+
+    pub fn to_statement(&self) -> AccountStatement {
+        let mut balance = 0;
+        let mut lines: Vec<StatementLine> = self.operations.iter()
+            .filter(|op| !op.charged_back)
+            .map(|op| {
+                balance += op.value();
+                StatementLine::Transaction {
+                    date: op.date,
+                    amount: raw_to_decimal(op.value()),
+                    balance: raw_to_decimal(balance)
+                }
+            }
+        ).collect();
+        lines.extend(self.checkpoints.iter().map(|checkpoint| StatementLine::BalanceAssertion {
+            date: checkpoint.at,
+            expected: checkpoint.expected,
+            actual: self.balance_at(checkpoint.at),
+        }));
+        lines.sort_by_key(|b| std::cmp::Reverse(b.date()));
+        AccountStatement { lines }
+    }
+
+    #[allow(dead_code)]
+    pub fn print_statement(&self) {
+        println!("{}", StatementLine::header());
+        for statement in self.to_statement().lines {
+            println!("{}", statement)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use std::str::FromStr;
+
+    fn money(value: &str) -> Amount<NonNegative> {
+        Amount::from_decimal(Decimal::from_str(value).unwrap()).unwrap()
+    }
+
+    fn decimal(value: &str) -> Decimal {
+        Decimal::from_str(value).unwrap()
+    }
+
+    #[test]
+    fn test_create_account() {
+
+        let account = BankAccount::new();
+        assert_eq!(account.balance().unwrap().to_decimal(), decimal("0"));
+    }
+
+    #[test]
+    fn test_make_deposit() {
+        let mut account = BankAccount::new();
+        account.make_deposit(1, money("100"), Utc::now()).unwrap();
+        assert_eq!(account.balance().unwrap().to_decimal(), decimal("100"));
+    }
+
+    #[test]
+    fn test_make_deposit_supports_up_to_four_decimal_places() {
+        let mut account = BankAccount::new();
+        account.make_deposit(1, money("2.742"), Utc::now()).unwrap();
+        account.make_deposit(2, money("1.5"), Utc::now()).unwrap();
+        assert_eq!(account.balance().unwrap().to_decimal(), decimal("4.242"));
+    }
+
+    #[test]
+    fn test_make_withdrawal() {
+        let mut account = BankAccount::new();
+        account.make_withdrawal(1, money("50"), Utc::now()).unwrap();
+        assert_eq!(account.balance().unwrap().to_decimal(), decimal("-50"));
+    }
+
+    #[test]
+    fn test_withdrawal_refused_if_balance_falls_below_50() {
+        let mut account = BankAccount::new();
+        let withdrawal = account.make_withdrawal(1, money("50.0001"), Utc::now());
+        assert_eq!(withdrawal, Err("Not enough money"));
+    }
+
+    #[test]
+    fn test_account_statement() {
+        let mut account = BankAccount::new();
+        account.make_deposit(1, money("10"), Utc.with_ymd_and_hms(2022, 1, 14, 8, 9, 10).unwrap()).unwrap();
+        account.make_deposit(2, money("20"), Utc.with_ymd_and_hms(2022, 1, 15, 8, 9, 10).unwrap()).unwrap();
+        account.make_withdrawal(3, money("15"), Utc.with_ymd_and_hms(2022, 1, 18, 8, 9, 10).unwrap()).unwrap();
+        let lines = vec![
+            StatementLine::Transaction {
+                date: Utc.with_ymd_and_hms(2022, 1, 18, 8, 9, 10).unwrap(),
+                amount: decimal("-15"),
+                balance: decimal("15")
+            },
+            StatementLine::Transaction {
+                date: Utc.with_ymd_and_hms(2022, 1, 15, 8, 9, 10).unwrap(),
+                amount: decimal("20"),
+                balance: decimal("30")
+            },
+            StatementLine::Transaction {
+                date: Utc.with_ymd_and_hms(2022, 1, 14, 8, 9, 10).unwrap(),
+                amount: decimal("10"),
+                balance: decimal("10")
+            }
+        ];
+        assert_eq!(account.to_statement(), AccountStatement {
+            lines
+        });
+    }
+
+    #[test]
+    fn test_dispute_moves_amount_from_available_to_held() {
+        let mut account = BankAccount::new();
+        account.make_deposit(1, money("100"), Utc::now()).unwrap();
+        account.dispute(1);
+        assert_eq!(account.balance().unwrap().to_decimal(), decimal("0"));
+        assert_eq!(account.held(), 1_000_000);
+        assert_eq!(account.total(), 1_000_000);
+    }
+
+    #[test]
+    fn test_dispute_unknown_tx_is_a_no_op() {
+        let mut account = BankAccount::new();
+        account.make_deposit(1, money("100"), Utc::now()).unwrap();
+        account.dispute(42);
+        assert_eq!(account.balance().unwrap().to_decimal(), decimal("100"));
+        assert_eq!(account.held(), 0);
+    }
+
+    #[test]
+    fn test_dispute_twice_is_a_no_op() {
+        let mut account = BankAccount::new();
+        account.make_deposit(1, money("100"), Utc::now()).unwrap();
+        account.dispute(1);
+        account.dispute(1);
+        assert_eq!(account.held(), 1_000_000);
+    }
+
+    #[test]
+    fn test_resolve_moves_amount_back_to_available() {
+        let mut account = BankAccount::new();
+        account.make_deposit(1, money("100"), Utc::now()).unwrap();
+        account.dispute(1);
+        account.resolve(1);
+        assert_eq!(account.balance().unwrap().to_decimal(), decimal("100"));
+        assert_eq!(account.held(), 0);
+        assert_eq!(account.total(), 1_000_000);
+    }
+
+    #[test]
+    fn test_resolve_without_dispute_is_a_no_op() {
+        let mut account = BankAccount::new();
+        account.make_deposit(1, money("100"), Utc::now()).unwrap();
+        account.resolve(1);
+        assert_eq!(account.balance().unwrap().to_decimal(), decimal("100"));
+        assert_eq!(account.held(), 0);
+    }
+
+    #[test]
+    fn test_chargeback_removes_held_amount_and_locks_account() {
+        let mut account = BankAccount::new();
+        account.make_deposit(1, money("100"), Utc::now()).unwrap();
+        account.dispute(1);
+        account.chargeback(1);
+        assert_eq!(account.balance().unwrap().to_decimal(), decimal("0"));
+        assert_eq!(account.held(), 0);
+        assert_eq!(account.total(), 0);
+        assert!(account.locked);
+    }
+
+    #[test]
+    fn test_chargeback_without_dispute_is_a_no_op() {
+        let mut account = BankAccount::new();
+        account.make_deposit(1, money("100"), Utc::now()).unwrap();
+        account.chargeback(1);
+        assert_eq!(account.balance().unwrap().to_decimal(), decimal("100"));
+        assert!(!account.locked);
+    }
+
+    #[test]
+    fn test_locked_account_refuses_future_operations() {
+        let mut account = BankAccount::new();
+        account.make_deposit(1, money("100"), Utc::now()).unwrap();
+        account.dispute(1);
+        account.chargeback(1);
+        assert_eq!(account.make_deposit(2, money("10"), Utc::now()), Err("Account is locked"));
+        assert_eq!(account.make_withdrawal(3, money("10"), Utc::now()), Err("Account is locked"));
+    }
+
+    #[test]
+    fn test_assert_balance_matches_expected() {
+        let mut account = BankAccount::new();
+        account.make_deposit(1, money("100"), Utc.with_ymd_and_hms(2022, 1, 14, 8, 9, 10).unwrap()).unwrap();
+        account.make_deposit(2, money("50"), Utc.with_ymd_and_hms(2022, 1, 20, 8, 9, 10).unwrap()).unwrap();
+        assert_eq!(account.assert_balance(decimal("100"), Utc.with_ymd_and_hms(2022, 1, 16, 0, 0, 0).unwrap()), Ok(()));
+    }
+
+    #[test]
+    fn test_assert_balance_reports_mismatch() {
+        let mut account = BankAccount::new();
+        account.make_deposit(1, money("100"), Utc.with_ymd_and_hms(2022, 1, 14, 8, 9, 10).unwrap()).unwrap();
+        let at = Utc.with_ymd_and_hms(2022, 1, 16, 0, 0, 0).unwrap();
+        assert_eq!(
+            account.assert_balance(decimal("150"), at),
+            Err(format!("expected balance 150 at {} but found 100", at))
+        );
+    }
+
+    #[test]
+    fn test_project_balance_capitalizes_interest_each_period() {
+        let mut account = BankAccount::new();
+        let start = Utc.with_ymd_and_hms(2022, 1, 1, 0, 0, 0).unwrap();
+        account.make_deposit(1, money("1000"), start).unwrap();
+        let projected = account.project_balance(decimal("0.01"), Duration::days(30), start + Duration::days(60));
+        assert_eq!(projected, decimal("1010"));
+    }
+
+    #[test]
+    fn test_project_balance_folds_in_deposits_within_a_step() {
+        let mut account = BankAccount::new();
+        let start = Utc.with_ymd_and_hms(2022, 1, 1, 0, 0, 0).unwrap();
+        account.make_deposit(1, money("1000"), start).unwrap();
+        account.make_deposit(2, money("500"), start + Duration::days(10)).unwrap();
+        let projected = account.project_balance(decimal("0"), Duration::days(30), start + Duration::days(30));
+        assert_eq!(projected, decimal("1500"));
+    }
+
+    #[test]
+    fn test_project_balance_accrues_no_interest_on_negative_balance() {
+        let mut account = BankAccount::new();
+        let start = Utc.with_ymd_and_hms(2022, 1, 1, 0, 0, 0).unwrap();
+        account.make_withdrawal(1, money("10"), start).unwrap();
+        let projected = account.project_balance(decimal("0.05"), Duration::days(30), start + Duration::days(60));
+        assert_eq!(projected, decimal("-10"));
+    }
+
+    #[test]
+    fn test_project_balance_includes_operation_dated_exactly_at_until() {
+        let mut account = BankAccount::new();
+        let start = Utc.with_ymd_and_hms(2022, 1, 1, 0, 0, 0).unwrap();
+        account.make_deposit(1, money("1000"), start).unwrap();
+        account.make_deposit(2, money("500"), start + Duration::days(15)).unwrap();
+        let projected = account.project_balance(decimal("0"), Duration::days(30), start + Duration::days(15));
+        assert_eq!(projected, decimal("1500"));
+    }
+
+    #[test]
+    fn test_project_balance_until_the_first_operations_date_reflects_it() {
+        let mut account = BankAccount::new();
+        let start = Utc.with_ymd_and_hms(2022, 1, 1, 0, 0, 0).unwrap();
+        account.make_deposit(1, money("1000"), start).unwrap();
+        let projected = account.project_balance(decimal("0.01"), Duration::days(30), start);
+        assert_eq!(projected, decimal("1000"));
+    }
+
+    #[test]
+    fn test_assert_balance_is_surfaced_as_a_statement_line() {
+        let mut account = BankAccount::new();
+        let deposit_date = Utc.with_ymd_and_hms(2022, 1, 14, 8, 9, 10).unwrap();
+        let assertion_date = Utc.with_ymd_and_hms(2022, 1, 16, 0, 0, 0).unwrap();
+        account.make_deposit(1, money("100"), deposit_date).unwrap();
+        let _ = account.assert_balance(decimal("100"), assertion_date);
+        let lines = account.to_statement().lines;
+        assert_eq!(lines[0], StatementLine::BalanceAssertion {
+            date: assertion_date,
+            expected: decimal("100"),
+            actual: decimal("100"),
+        });
+    }
+}
@@ -0,0 +1,172 @@
+use rust_decimal::Decimal;
+use std::convert::TryFrom;
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::{Add, RangeInclusive, Sub};
+
+/// Amounts are stored internally as a fixed-point `i64` counting
+/// ten-thousandths, giving up to four decimal places of precision.
+pub const SCALE: i64 = 10_000;
+
+/// Converts a raw ten-thousandths value into a normalized `Decimal`, so callers
+/// never have to strip insignificant trailing zeros themselves.
+pub fn raw_to_decimal(raw: i64) -> Decimal {
+    Decimal::new(raw, 4).normalize()
+}
+
+/// Bounds the set of `i64` values (in ten-thousandths) a given `Amount<C>` may hold.
+pub trait Constraint {
+    fn valid_range() -> RangeInclusive<i64>;
+}
+
+/// Deposits and withdrawals are always expressed as a non-negative amount.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct NonNegative;
+
+impl Constraint for NonNegative {
+    fn valid_range() -> RangeInclusive<i64> {
+        0..=i64::MAX
+    }
+}
+
+/// An account balance is allowed to dip into the agreed overdraft.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct OverdraftAllowed;
+
+impl Constraint for OverdraftAllowed {
+    fn valid_range() -> RangeInclusive<i64> {
+        -50 * SCALE..=i64::MAX
+    }
+}
+
+#[derive(PartialEq, Debug)]
+pub enum AmountError {
+    OutOfRange(i64),
+    Overflow,
+    TooManyDecimals(Decimal),
+}
+
+impl fmt::Display for AmountError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AmountError::OutOfRange(value) => write!(f, "{} is outside the valid range", value),
+            AmountError::Overflow => write!(f, "amount arithmetic overflowed"),
+            AmountError::TooManyDecimals(value) => write!(f, "{} has more than four decimal places", value),
+        }
+    }
+}
+
+/// A fixed-point amount that can only ever hold values allowed by `C`.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct Amount<C: Constraint>(i64, PhantomData<C>);
+
+impl<C: Constraint> Amount<C> {
+    pub fn value(&self) -> i64 {
+        self.0
+    }
+
+    /// Re-validates this amount against a different constraint.
+    pub fn constrain<C2: Constraint>(&self) -> Result<Amount<C2>, AmountError> {
+        Amount::try_from(self.0)
+    }
+
+    /// Parses a decimal amount, rejecting more than four *significant*
+    /// fractional digits (trailing-zero padding like `1.10000` is fine).
+    pub fn from_decimal(value: Decimal) -> Result<Self, AmountError> {
+        if value.round_dp(4) != value {
+            return Err(AmountError::TooManyDecimals(value));
+        }
+        let mut rescaled = value;
+        rescaled.rescale(4);
+        let raw = i64::try_from(rescaled.mantissa()).map_err(|_| AmountError::Overflow)?;
+        Amount::try_from(raw)
+    }
+
+    pub fn to_decimal(&self) -> Decimal {
+        raw_to_decimal(self.0)
+    }
+}
+
+impl<C: Constraint> TryFrom<i64> for Amount<C> {
+    type Error = AmountError;
+
+    fn try_from(value: i64) -> Result<Self, Self::Error> {
+        if C::valid_range().contains(&value) {
+            Ok(Amount(value, PhantomData))
+        } else {
+            Err(AmountError::OutOfRange(value))
+        }
+    }
+}
+
+impl<C: Constraint> Add for Amount<C> {
+    type Output = Result<Amount<C>, AmountError>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let value = self.0.checked_add(rhs.0).ok_or(AmountError::Overflow)?;
+        Amount::try_from(value)
+    }
+}
+
+impl<C: Constraint> Sub for Amount<C> {
+    type Output = Result<Amount<C>, AmountError>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let value = self.0.checked_sub(rhs.0).ok_or(AmountError::Overflow)?;
+        Amount::try_from(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_non_negative_rejects_negative_values() {
+        assert_eq!(Amount::<NonNegative>::try_from(-1), Err(AmountError::OutOfRange(-1)));
+        assert!(Amount::<NonNegative>::try_from(0).is_ok());
+    }
+
+    #[test]
+    fn test_overdraft_allowed_rejects_below_floor() {
+        assert_eq!(Amount::<OverdraftAllowed>::try_from(-50 * SCALE - 1), Err(AmountError::OutOfRange(-50 * SCALE - 1)));
+        assert!(Amount::<OverdraftAllowed>::try_from(-50 * SCALE).is_ok());
+    }
+
+    #[test]
+    fn test_add_and_sub_return_results() {
+        let a = Amount::<OverdraftAllowed>::try_from(10).unwrap();
+        let b = Amount::<OverdraftAllowed>::try_from(30).unwrap();
+        assert_eq!((a + b).unwrap().value(), 40);
+        assert_eq!(b - a, Amount::try_from(20));
+    }
+
+    #[test]
+    fn test_constrain_revalidates_against_other_constraint() {
+        let non_negative = Amount::<NonNegative>::try_from(5).unwrap();
+        let overdraft: Amount<OverdraftAllowed> = non_negative.constrain().unwrap();
+        assert_eq!(overdraft.value(), 5);
+    }
+
+    #[test]
+    fn test_from_decimal_supports_up_to_four_fractional_digits() {
+        let amount = Amount::<NonNegative>::from_decimal(Decimal::from_str("2.742").unwrap()).unwrap();
+        assert_eq!(amount.to_decimal(), Decimal::from_str("2.742").unwrap());
+
+        let amount = Amount::<NonNegative>::from_decimal(Decimal::from_str("1.5").unwrap()).unwrap();
+        assert_eq!(amount.to_decimal(), Decimal::from_str("1.5").unwrap());
+    }
+
+    #[test]
+    fn test_from_decimal_accepts_insignificant_trailing_zero_padding() {
+        let amount = Amount::<NonNegative>::from_decimal(Decimal::from_str("1.10000").unwrap()).unwrap();
+        assert_eq!(amount.to_decimal(), Decimal::from_str("1.1").unwrap());
+    }
+
+    #[test]
+    fn test_from_decimal_rejects_more_than_four_fractional_digits() {
+        let value = Decimal::from_str("1.23456").unwrap();
+        assert_eq!(Amount::<NonNegative>::from_decimal(value), Err(AmountError::TooManyDecimals(value)));
+    }
+}
@@ -0,0 +1,96 @@
+use crate::account::TransactionId;
+use crate::amount::Amount;
+use crate::store::{ActStore, ClientId};
+use chrono::Utc;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::io::Read;
+
+#[derive(Debug, Deserialize)]
+struct Row {
+    #[serde(rename = "type")]
+    kind: String,
+    client: ClientId,
+    tx: TransactionId,
+    amount: Option<Decimal>,
+}
+
+/// Streams a CSV of `type,client,tx,amount` rows into `store`, row by row so
+/// that large inputs never need to be held in memory all at once. Rows that
+/// are malformed, or reference an unknown operation, are skipped rather than
+/// aborting the whole ingest.
+pub fn ingest_csv<R: Read>(reader: R, store: &mut ActStore) {
+    let mut csv_reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(reader);
+    for row in csv_reader.deserialize::<Row>().flatten() {
+        apply_row(store, row);
+    }
+}
+
+// malformed or rejected rows are silently skipped rather than aborting the ingest;
+// the account is only looked up for recognized kinds so a garbage row never
+// creates a phantom all-zero account for a client that was never actually seen
+fn apply_row(store: &mut ActStore, row: Row) {
+    let _ = match row.kind.as_str() {
+        "deposit" => deposit_amount(row.amount)
+            .and_then(|money| store.account_mut(row.client).make_deposit(row.tx, money, Utc::now())),
+        "withdrawal" => deposit_amount(row.amount)
+            .and_then(|money| store.account_mut(row.client).make_withdrawal(row.tx, money, Utc::now())),
+        "dispute" => {
+            store.account_mut(row.client).dispute(row.tx);
+            Ok(())
+        }
+        "resolve" => {
+            store.account_mut(row.client).resolve(row.tx);
+            Ok(())
+        }
+        "chargeback" => {
+            store.account_mut(row.client).chargeback(row.tx);
+            Ok(())
+        }
+        _ => Err("unknown operation type"),
+    };
+}
+
+fn deposit_amount(amount: Option<Decimal>) -> Result<Amount<crate::amount::NonNegative>, &'static str> {
+    let amount = amount.ok_or("missing amount")?;
+    Amount::from_decimal(amount).map_err(|_| "invalid amount")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ingest_dispatches_rows_to_the_right_client() {
+        let csv = "type,client,tx,amount\n\
+                   deposit,1,1,100\n\
+                   deposit,2,1,5\n\
+                   dispute,1,1,\n";
+        let mut store = ActStore::new();
+        ingest_csv(csv.as_bytes(), &mut store);
+        assert_eq!(store.to_summary_csv(), "client,available,held,total,locked\n1,0,100,100,false\n2,5,0,5,false\n");
+    }
+
+    #[test]
+    fn test_ingest_skips_malformed_rows() {
+        let csv = "type,client,tx,amount\n\
+                   deposit,1,1,100\n\
+                   deposit,not-a-client,2,10\n\
+                   withdrawal,1,3,40\n";
+        let mut store = ActStore::new();
+        ingest_csv(csv.as_bytes(), &mut store);
+        assert_eq!(store.to_summary_csv(), "client,available,held,total,locked\n1,60,0,60,false\n");
+    }
+
+    #[test]
+    fn test_ingest_does_not_create_a_phantom_account_for_an_unknown_operation() {
+        let csv = "type,client,tx,amount\n\
+                   deposit,1,1,100\n\
+                   bogus,5,2,10\n";
+        let mut store = ActStore::new();
+        ingest_csv(csv.as_bytes(), &mut store);
+        assert_eq!(store.to_summary_csv(), "client,available,held,total,locked\n1,100,0,100,false\n");
+    }
+}
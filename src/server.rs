@@ -0,0 +1,183 @@
+use crate::account::{BankAccount, TransactionId};
+use crate::amount::{Amount, NonNegative};
+use crate::store::{ActStore, ClientId};
+use chrono::Utc;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::io::Cursor;
+use std::sync::{Arc, Mutex};
+use tiny_http::{Method, Request, Response, Server};
+
+#[derive(Deserialize)]
+struct OperationPayload {
+    tx_id: TransactionId,
+    amount: Decimal,
+}
+
+impl OperationPayload {
+    fn money(&self) -> Result<Amount<NonNegative>, &'static str> {
+        Amount::from_decimal(self.amount).map_err(|_| "Invalid amount")
+    }
+}
+
+#[derive(Serialize)]
+struct ErrorPayload {
+    reason: String,
+}
+
+type JsonResponse = Response<Cursor<Vec<u8>>>;
+
+type OperationFn = for<'a> fn(&'a mut BankAccount, TransactionId, Amount<NonNegative>, chrono::DateTime<Utc>) -> Result<(), &'a str>;
+
+/// Serves the `BankAccount` operations over HTTP, behind a shared, thread-safe store.
+pub fn run(address: &str, store: Arc<Mutex<ActStore>>) {
+    let server = Server::http(address).expect("failed to bind HTTP server");
+    for request in server.incoming_requests() {
+        dispatch(request, &store);
+    }
+}
+
+fn dispatch(mut request: Request, store: &Arc<Mutex<ActStore>>) {
+    let method = request.method().clone();
+    let url = request.url().to_string();
+    let segments: Vec<&str> = url.trim_matches('/').split('/').collect();
+    let response = match (method, segments.as_slice()) {
+        (Method::Post, ["accounts", client, "deposit"]) => handle_deposit(&mut request, store, client),
+        (Method::Post, ["accounts", client, "withdraw"]) => handle_withdraw(&mut request, store, client),
+        (Method::Get, ["accounts", client, "statement"]) => handle_statement(store, client),
+        _ => error_response(404, "Not found"),
+    };
+    let _ = request.respond(response);
+}
+
+fn handle_deposit(request: &mut Request, store: &Arc<Mutex<ActStore>>, client: &str) -> JsonResponse {
+    handle_operation(request, store, client, BankAccount::make_deposit)
+}
+
+fn handle_withdraw(request: &mut Request, store: &Arc<Mutex<ActStore>>, client: &str) -> JsonResponse {
+    handle_operation(request, store, client, BankAccount::make_withdrawal)
+}
+
+fn handle_operation(
+    request: &mut Request,
+    store: &Arc<Mutex<ActStore>>,
+    client: &str,
+    operation: OperationFn,
+) -> JsonResponse {
+    let client_id = match client.parse::<ClientId>() {
+        Ok(id) => id,
+        Err(_) => return error_response(400, "Invalid client id"),
+    };
+    let payload: OperationPayload = match serde_json::from_reader(request.as_reader()) {
+        Ok(payload) => payload,
+        Err(_) => return error_response(400, "Invalid request body"),
+    };
+    let money = match payload.money() {
+        Ok(money) => money,
+        Err(reason) => return error_response(400, reason),
+    };
+
+    let mut store = store.lock().unwrap();
+    let account = store.account_mut(client_id);
+    match operation(account, payload.tx_id, money, Utc::now()) {
+        Ok(()) => json_response(200, &account.to_statement()),
+        Err(reason) => error_response(400, reason),
+    }
+}
+
+fn handle_statement(store: &Arc<Mutex<ActStore>>, client: &str) -> JsonResponse {
+    let client_id = match client.parse::<ClientId>() {
+        Ok(id) => id,
+        Err(_) => return error_response(400, "Invalid client id"),
+    };
+    let store = store.lock().unwrap();
+    match store.account(client_id) {
+        Some(account) => json_response(200, &account.to_statement()),
+        None => error_response(404, "Unknown client"),
+    }
+}
+
+fn json_response<T: Serialize>(status: u16, body: &T) -> JsonResponse {
+    let bytes = serde_json::to_vec(body).expect("serializing a response body never fails");
+    Response::from_data(bytes)
+        .with_status_code(status)
+        .with_header(tiny_http::Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..]).unwrap())
+}
+
+fn error_response(status: u16, reason: &str) -> JsonResponse {
+    json_response(status, &ErrorPayload { reason: reason.to_string() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+    use std::thread;
+
+    // starts a real server on an OS-assigned port and serves requests on a
+    // background thread for the lifetime of the test process
+    fn spawn_server() -> String {
+        let server = Server::http("127.0.0.1:0").expect("failed to bind HTTP server");
+        let addr = server.server_addr().to_ip().expect("server is bound to an IP address");
+        let store = Arc::new(Mutex::new(ActStore::new()));
+        thread::spawn(move || {
+            for request in server.incoming_requests() {
+                dispatch(request, &store);
+            }
+        });
+        addr.to_string()
+    }
+
+    // sends a raw HTTP/1.1 request and returns (status code, body)
+    fn send_request(addr: &str, method: &str, path: &str, body: &str) -> (u16, String) {
+        let mut stream = TcpStream::connect(addr).expect("failed to connect to test server");
+        let request = format!(
+            "{method} {path} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        );
+        stream.write_all(request.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let mut parts = response.splitn(2, "\r\n\r\n");
+        let status = parts.next().unwrap()
+            .lines().next().unwrap()
+            .split_whitespace().nth(1).unwrap()
+            .parse().unwrap();
+        (status, parts.next().unwrap_or("").to_string())
+    }
+
+    #[test]
+    fn test_deposit_then_statement_roundtrip() {
+        let addr = spawn_server();
+        let (status, body) = send_request(&addr, "POST", "/accounts/1/deposit", r#"{"tx_id":1,"amount":"100"}"#);
+        assert_eq!(status, 200);
+        assert!(body.contains("100"), "expected statement to contain the deposit, got {body}");
+
+        let (status, body) = send_request(&addr, "GET", "/accounts/1/statement", "");
+        assert_eq!(status, 200);
+        assert!(body.contains("100"), "expected statement to contain the deposit, got {body}");
+    }
+
+    #[test]
+    fn test_withdrawal_rejection_maps_to_400_with_reason() {
+        let addr = spawn_server();
+        let (status, body) = send_request(&addr, "POST", "/accounts/1/withdraw", r#"{"tx_id":1,"amount":"50.0001"}"#);
+        assert_eq!(status, 400);
+        assert!(body.contains("Not enough money"), "expected rejection reason, got {body}");
+    }
+
+    #[test]
+    fn test_unknown_route_is_404() {
+        let addr = spawn_server();
+        let (status, _) = send_request(&addr, "GET", "/nope", "");
+        assert_eq!(status, 404);
+    }
+
+    #[test]
+    fn test_statement_for_unknown_client_is_404() {
+        let addr = spawn_server();
+        let (status, _) = send_request(&addr, "GET", "/accounts/1/statement", "");
+        assert_eq!(status, 404);
+    }
+}
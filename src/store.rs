@@ -0,0 +1,66 @@
+use crate::account::BankAccount;
+use crate::amount::raw_to_decimal;
+use std::collections::HashMap;
+
+pub type ClientId = u16;
+
+/// A ledger of per-client accounts, keyed by client id, as tracked by the `act` engine.
+pub struct ActStore {
+    accounts: HashMap<ClientId, BankAccount>,
+}
+
+impl ActStore {
+    pub fn new() -> ActStore {
+        ActStore {
+            accounts: HashMap::new(),
+        }
+    }
+
+    pub fn account_mut(&mut self, client: ClientId) -> &mut BankAccount {
+        self.accounts.entry(client).or_insert_with(BankAccount::new)
+    }
+
+    pub fn account(&self, client: ClientId) -> Option<&BankAccount> {
+        self.accounts.get(&client)
+    }
+
+    /// Renders every client's final state as `client,available,held,total,locked` CSV rows.
+    pub fn to_summary_csv(&self) -> String {
+        let mut clients: Vec<&ClientId> = self.accounts.keys().collect();
+        clients.sort();
+        let mut out = String::from("client,available,held,total,locked\n");
+        for client in clients {
+            let account = &self.accounts[client];
+            let available = account.balance().map(|a| a.to_decimal()).unwrap_or_default();
+            let held = raw_to_decimal(account.held());
+            let total = raw_to_decimal(account.total());
+            out.push_str(&format!("{},{},{},{},{}\n", client, available, held, total, account.locked));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_creates_one_account_per_client() {
+        let mut store = ActStore::new();
+        store.account_mut(1).make_deposit(1, crate::amount::Amount::from_decimal(Decimal::from_str("10").unwrap()).unwrap(), Utc::now()).unwrap();
+        store.account_mut(2).make_deposit(1, crate::amount::Amount::from_decimal(Decimal::from_str("5").unwrap()).unwrap(), Utc::now()).unwrap();
+        assert_eq!(store.accounts.len(), 2);
+    }
+
+    #[test]
+    fn test_summary_csv_lists_clients_in_order() {
+        let mut store = ActStore::new();
+        store.account_mut(2).make_deposit(1, crate::amount::Amount::from_decimal(Decimal::from_str("5").unwrap()).unwrap(), Utc::now()).unwrap();
+        store.account_mut(1).make_deposit(1, crate::amount::Amount::from_decimal(Decimal::from_str("10").unwrap()).unwrap(), Utc::now()).unwrap();
+        let csv = store.to_summary_csv();
+        assert_eq!(csv, "client,available,held,total,locked\n1,10,0,10,false\n2,5,0,5,false\n");
+    }
+}